@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+use backend::{strava, supabase};
+use vercel_runtime::{run, Body, Error, Request, Response, StatusCode};
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    run(handler).await
+}
+
+/// GET /api/oauth_callback - Strava OAuth authorization redirect. Exchanges
+/// the `code` Strava appended to the redirect for an access/refresh token
+/// pair and stores it, so `tasks::execute` has a token to work with the
+/// next time a webhook event comes in for this athlete.
+pub async fn handler(req: Request) -> Result<Response<Body>, Error> {
+    let query = req.uri().query().unwrap_or("");
+    let params: HashMap<String, String> = serde_urlencoded::from_str(query).unwrap_or_default();
+
+    let Some(code) = params.get("code") else {
+        return Ok(Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::Text("Missing `code` query parameter".to_string()))?);
+    };
+
+    let client_id = std::env::var("STRAVA_CLIENT_ID").unwrap_or_default();
+    let client_secret = std::env::var("STRAVA_CLIENT_SECRET").unwrap_or_default();
+
+    match strava::exchange_token(&client_id, &client_secret, code).await {
+        Ok((owner_id, token)) => match supabase::save_token(owner_id, &token).await {
+            Ok(()) => Ok(Response::builder()
+                .status(StatusCode::OK)
+                .body(Body::Text("Strava account connected".to_string()))?),
+            Err(e) => {
+                let status = StatusCode::from_u16(e.status_code().as_u16())
+                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+                Ok(Response::builder()
+                    .status(status)
+                    .body(Body::Text(format!("Error: {}", e)))?)
+            }
+        },
+        Err(e) => {
+            let status = StatusCode::from_u16(e.status_code().as_u16())
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            Ok(Response::builder()
+                .status(status)
+                .body(Body::Text(format!("Error: {}", e)))?)
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use backend::stats::{self, StatsQuery};
+use vercel_runtime::{run, Body, Error, Request, Response, StatusCode};
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    run(handler).await
+}
+
+pub async fn handler(req: Request) -> Result<Response<Body>, Error> {
+    match *req.method() {
+        http::Method::GET => get_stats(req).await,
+        _ => Ok(Response::builder()
+            .status(StatusCode::METHOD_NOT_ALLOWED)
+            .body(Body::Empty)?),
+    }
+}
+
+async fn get_stats(req: Request) -> Result<Response<Body>, Error> {
+    let query_str = req.uri().query().unwrap_or("");
+    let query: StatsQuery = serde_urlencoded::from_str(query_str).unwrap_or_default();
+
+    match stats::compute(&query).await {
+        Ok(payload) => Ok(Response::builder()
+            .status(StatusCode::OK)
+            .header("Content-Type", "application/json")
+            .body(Body::Text(serde_json::to_string(&payload)?))?),
+        Err(e) => {
+            let status = StatusCode::from_u16(e.status_code().as_u16())
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            Ok(Response::builder()
+                .status(status)
+                .body(Body::Text(format!("{}", e)))?)
+        }
+    }
+}
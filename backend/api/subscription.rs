@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use backend::strava;
+use vercel_runtime::{run, Body, Error, Request, Response, StatusCode};
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    run(handler).await
+}
+
+pub async fn handler(req: Request) -> Result<Response<Body>, Error> {
+    match *req.method() {
+        http::Method::GET => view_subscription(req).await,
+        http::Method::POST => create_subscription(req).await,
+        http::Method::DELETE => delete_subscription(req).await,
+        _ => Ok(Response::builder()
+            .status(StatusCode::METHOD_NOT_ALLOWED)
+            .body(Body::Empty)?),
+    }
+}
+
+fn credentials() -> (String, String) {
+    (
+        std::env::var("STRAVA_CLIENT_ID").unwrap_or_default(),
+        std::env::var("STRAVA_CLIENT_SECRET").unwrap_or_default(),
+    )
+}
+
+async fn view_subscription(_req: Request) -> Result<Response<Body>, Error> {
+    let (client_id, client_secret) = credentials();
+
+    match strava::view_subscription(&client_id, &client_secret).await {
+        Ok(Some(subscription)) => Ok(Response::builder()
+            .status(StatusCode::OK)
+            .header("Content-Type", "application/json")
+            .body(Body::Text(serde_json::to_string(&subscription)?))?),
+        Ok(None) => Ok(Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::Empty)?),
+        Err(e) => {
+            let status = StatusCode::from_u16(e.status_code().as_u16())
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            Ok(Response::builder()
+                .status(status)
+                .body(Body::Text(format!("Error: {}", e)))?)
+        }
+    }
+}
+
+async fn create_subscription(_req: Request) -> Result<Response<Body>, Error> {
+    let (client_id, client_secret) = credentials();
+    let callback_url = std::env::var("STRAVA_CALLBACK_URL").unwrap_or_default();
+    let verify_token = std::env::var("STRAVA_VERIFY_TOKEN").unwrap_or_default();
+
+    match strava::create_subscription(&client_id, &client_secret, &callback_url, &verify_token).await {
+        Ok(id) => Ok(Response::builder()
+            .status(StatusCode::CREATED)
+            .header("Content-Type", "application/json")
+            .body(Body::Text(serde_json::to_string(&serde_json::json!({ "id": id }))?))?),
+        Err(e) => {
+            let status = StatusCode::from_u16(e.status_code().as_u16())
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            Ok(Response::builder()
+                .status(status)
+                .body(Body::Text(format!("Error: {}", e)))?)
+        }
+    }
+}
+
+async fn delete_subscription(req: Request) -> Result<Response<Body>, Error> {
+    let (client_id, client_secret) = credentials();
+    let query = req.uri().query().unwrap_or("");
+    let params: HashMap<String, String> = serde_urlencoded::from_str(query).unwrap_or_default();
+
+    let Some(id) = params.get("id").and_then(|s| s.parse::<i64>().ok()) else {
+        return Ok(Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::Text("Missing or invalid `id` query parameter".to_string()))?);
+    };
+
+    match strava::delete_subscription(&client_id, &client_secret, id).await {
+        Ok(()) => Ok(Response::builder()
+            .status(StatusCode::NO_CONTENT)
+            .body(Body::Empty)?),
+        Err(e) => {
+            let status = StatusCode::from_u16(e.status_code().as_u16())
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            Ok(Response::builder()
+                .status(status)
+                .body(Body::Text(format!("Error: {}", e)))?)
+        }
+    }
+}
@@ -1,33 +1,6 @@
-use serde::{Deserialize, Serialize};
+use backend::webhook::{self, VerificationResponse, WebhookEvent, WebhookVerification};
 use vercel_runtime::{run, Body, Error, Request, Response, StatusCode};
 
-#[derive(Debug, Deserialize)]
-struct WebhookVerification {
-    #[serde(rename = "hub.mode")]
-    mode: Option<String>,
-    #[serde(rename = "hub.challenge")]
-    challenge: Option<String>,
-    #[serde(rename = "hub.verify_token")]
-    verify_token: Option<String>,
-}
-
-#[derive(Debug, Serialize)]
-struct VerificationResponse {
-    #[serde(rename = "hub.challenge")]
-    challenge: String,
-}
-
-#[derive(Debug, Deserialize)]
-struct WebhookEvent {
-    object_type: String,
-    object_id: i64,
-    aspect_type: String,
-    owner_id: i64,
-    subscription_id: i64,
-    event_time: i64,
-    updates: Option<serde_json::Value>,
-}
-
 #[tokio::main]
 async fn main() -> Result<(), Error> {
     run(handler).await
@@ -53,15 +26,15 @@ async fn handle_verification(req: Request) -> Result<Response<Body>, Error> {
 
     let verify_token = std::env::var("STRAVA_VERIFY_TOKEN").unwrap_or_default();
 
-    match (params.mode.as_deref(), params.verify_token.as_deref(), params.challenge) {
-        (Some("subscribe"), Some(token), Some(challenge)) if token == verify_token => {
+    match webhook::verify_challenge(&params, &verify_token) {
+        Some(challenge) => {
             let response = VerificationResponse { challenge };
             Ok(Response::builder()
                 .status(StatusCode::OK)
                 .header("Content-Type", "application/json")
                 .body(Body::Text(serde_json::to_string(&response)?))?)
         }
-        _ => Ok(Response::builder()
+        None => Ok(Response::builder()
             .status(StatusCode::FORBIDDEN)
             .body(Body::Text("Verification failed".to_string()))?),
     }
@@ -83,22 +56,8 @@ async fn handle_webhook(req: Request) -> Result<Response<Body>, Error> {
             .body(Body::Text("Invalid JSON".to_string()))?),
     };
 
-    // Only process activity events
-    if event.object_type == "activity" {
-        match event.aspect_type.as_str() {
-            "create" | "update" => {
-                // TODO: Fetch full activity from Strava API and store in Supabase
-                println!(
-                    "Processing activity {} for athlete {}",
-                    event.object_id, event.owner_id
-                );
-            }
-            "delete" => {
-                // TODO: Handle activity deletion
-                println!("Activity {} deleted", event.object_id);
-            }
-            _ => {}
-        }
+    if let Err(e) = webhook::dispatch(&event).await {
+        eprintln!("failed to process webhook event: {}", e);
     }
 
     Ok(Response::builder()
@@ -0,0 +1,113 @@
+//! Crate-wide error type unifying the failure modes of the Strava and
+//! Supabase HTTP calls this crate makes, so handlers can map a failure to
+//! a meaningful HTTP status instead of always returning 500.
+
+use http::StatusCode;
+
+/// A single error object from Strava's JSON error body, e.g.
+/// `{"resource":"Activities","field":"id","code":"invalid"}`.
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct StravaApiErrorDetail {
+    pub code: String,
+    pub field: String,
+    #[serde(default)]
+    pub value: serde_json::Value,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct StravaErrorBody {
+    #[serde(default)]
+    errors: Vec<StravaApiErrorDetail>,
+}
+
+/// A failed call to the Strava API, carrying the HTTP status and the
+/// first error Strava reported in the response body, if any.
+#[derive(Debug)]
+pub struct StravaApiError {
+    pub status: StatusCode,
+    pub detail: Option<StravaApiErrorDetail>,
+}
+
+impl StravaApiError {
+    /// Builds a `StravaApiError` from a non-2xx `reqwest::Response`,
+    /// parsing Strava's `{"errors": [...]}` body if present.
+    pub async fn from_response(response: reqwest::Response) -> Self {
+        let status = response.status();
+        let detail = response
+            .json::<StravaErrorBody>()
+            .await
+            .ok()
+            .and_then(|body| body.errors.into_iter().next());
+
+        StravaApiError { status, detail }
+    }
+}
+
+impl std::fmt::Display for StravaApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.detail {
+            Some(d) => write!(
+                f,
+                "strava api error ({}): {} {} {:?}",
+                self.status, d.code, d.field, d.value
+            ),
+            None => write!(f, "strava api error ({})", self.status),
+        }
+    }
+}
+
+impl std::error::Error for StravaApiError {}
+
+/// Unifies every failure mode this crate's HTTP handlers can hit.
+#[derive(Debug)]
+pub enum Error {
+    Strava(StravaApiError),
+    Supabase(String),
+    Http(reqwest::Error),
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Strava(e) => write!(f, "{}", e),
+            Error::Supabase(e) => write!(f, "supabase error: {}", e),
+            Error::Http(e) => write!(f, "http error: {}", e),
+            Error::Json(e) => write!(f, "json error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<StravaApiError> for Error {
+    fn from(e: StravaApiError) -> Self {
+        Error::Strava(e)
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(e: reqwest::Error) -> Self {
+        Error::Http(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+impl Error {
+    /// Maps this error to the HTTP status handlers should respond with.
+    /// Strava's own status (e.g. 401 on an expired token, 429 when rate
+    /// limited) is propagated as-is so callers can react to it.
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Error::Strava(e) => e.status,
+            Error::Supabase(_) => StatusCode::BAD_GATEWAY,
+            Error::Http(_) => StatusCode::BAD_GATEWAY,
+            Error::Json(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod error;
+pub mod models;
+pub mod stats;
+pub mod strava;
+pub mod supabase;
+pub mod tasks;
+pub mod webhook;
@@ -2,15 +2,18 @@ use axum::{routing::get, Router};
 use std::net::SocketAddr;
 use tracing_subscriber;
 
-mod webhook;
+mod routes;
 
 #[tokio::main]
 async fn main() {
     tracing_subscriber::init();
 
+    tokio::spawn(backend::tasks::run_worker());
+
     let app = Router::new()
         .route("/", get(|| async { "Strava Backend API" }))
-        .route("/api/webhook", get(webhook::verify).post(webhook::handle));
+        .route("/api/webhook", get(routes::verify).post(routes::handle))
+        .route("/api/stats", get(routes::stats));
 
     let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
     tracing::info!("listening on {}", addr);
@@ -0,0 +1,22 @@
+//! Data shapes shared between the Strava API responses and the rows we
+//! persist to Supabase.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Activity {
+    pub id: i64,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub activity_type: String,
+    pub distance: f64,
+    pub moving_time: i32,
+    pub elapsed_time: i32,
+    pub total_elevation_gain: f64,
+    pub start_date: String,
+    pub average_speed: Option<f64>,
+    pub max_speed: Option<f64>,
+    pub average_heartrate: Option<f64>,
+    pub max_heartrate: Option<i32>,
+    pub calories: Option<i32>,
+}
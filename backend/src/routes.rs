@@ -0,0 +1,47 @@
+//! Axum route handlers for the local dev server. These are thin wrappers
+//! around the shared webhook logic in `backend::webhook` and the stats
+//! aggregation in `backend::stats`.
+
+use axum::{extract::Query, http::StatusCode, Json};
+use backend::stats::{self, Stats, StatsQuery};
+use backend::webhook::{self, VerificationResponse, WebhookEvent, WebhookVerification};
+
+/// GET /api/webhook - Strava webhook verification
+pub async fn verify(
+    Query(params): Query<WebhookVerification>,
+) -> Result<Json<VerificationResponse>, StatusCode> {
+    let verify_token = std::env::var("STRAVA_VERIFY_TOKEN").unwrap_or_default();
+
+    match webhook::verify_challenge(&params, &verify_token) {
+        Some(challenge) => {
+            tracing::info!("Webhook verified successfully");
+            Ok(Json(VerificationResponse { challenge }))
+        }
+        None => {
+            tracing::warn!("Webhook verification failed");
+            Err(StatusCode::FORBIDDEN)
+        }
+    }
+}
+
+/// POST /api/webhook - Receive Strava webhook events
+pub async fn handle(Json(event): Json<WebhookEvent>) -> StatusCode {
+    tracing::info!("Received webhook event: {:?}", event);
+
+    if let Err(e) = webhook::dispatch(&event).await {
+        tracing::error!("failed to process webhook event: {}", e);
+    }
+
+    StatusCode::OK
+}
+
+/// GET /api/stats - Aggregated activity totals and trends
+pub async fn stats(Query(query): Query<StatsQuery>) -> Result<Json<Stats>, StatusCode> {
+    match stats::compute(&query).await {
+        Ok(stats) => Ok(Json(stats)),
+        Err(e) => {
+            tracing::error!("failed to compute stats: {}", e);
+            Err(e.status_code())
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! Server-side aggregation over stored activities: per-sport totals,
+//! weekly/monthly rollups, and trailing moving averages, so a client can
+//! render trends without re-deriving them from the raw activity list.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Datelike, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::models::Activity;
+use crate::supabase;
+
+const TRAILING_WEEKS: i64 = 4;
+
+/// Query parameters accepted by the stats endpoint: an optional date
+/// range and activity-type filter, applied before aggregation.
+#[derive(Debug, Default, Deserialize)]
+pub struct StatsQuery {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub activity_type: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct TypeTotals {
+    pub activity_type: String,
+    pub count: u32,
+    pub distance: f64,
+    pub moving_time: i64,
+    pub total_elevation_gain: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PeriodRollup {
+    pub period: String,
+    pub count: u32,
+    pub distance: f64,
+    pub moving_time: i64,
+    pub total_elevation_gain: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TrailingAverages {
+    pub weeks: i64,
+    pub average_distance: f64,
+    pub average_pace_secs_per_km: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Stats {
+    pub by_type: Vec<TypeTotals>,
+    pub weekly: Vec<PeriodRollup>,
+    pub monthly: Vec<PeriodRollup>,
+    pub trailing: TrailingAverages,
+}
+
+/// Loads the activities matching `query` and computes the full stats
+/// payload over them.
+pub async fn compute(query: &StatsQuery) -> Result<Stats, Error> {
+    let activities = supabase::list_activities(query).await?;
+
+    Ok(Stats {
+        by_type: totals_by_type(&activities),
+        weekly: rollup(&activities, iso_week_key),
+        monthly: rollup(&activities, |date| format!("{}-{:02}", date.year(), date.month())),
+        trailing: trailing_averages(&activities),
+    })
+}
+
+fn parsed_date(activity: &Activity) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(&activity.start_date)
+        .ok()
+        .map(|d| d.with_timezone(&Utc))
+}
+
+fn iso_week_key(date: DateTime<Utc>) -> String {
+    let week = date.iso_week();
+    format!("{}-W{:02}", week.year(), week.week())
+}
+
+fn totals_by_type(activities: &[Activity]) -> Vec<TypeTotals> {
+    let mut by_type: BTreeMap<String, TypeTotals> = BTreeMap::new();
+
+    for activity in activities {
+        let entry = by_type
+            .entry(activity.activity_type.clone())
+            .or_insert_with(|| TypeTotals {
+                activity_type: activity.activity_type.clone(),
+                ..Default::default()
+            });
+        entry.count += 1;
+        entry.distance += activity.distance;
+        entry.moving_time += activity.moving_time as i64;
+        entry.total_elevation_gain += activity.total_elevation_gain;
+    }
+
+    by_type.into_values().collect()
+}
+
+fn rollup(activities: &[Activity], period_key: impl Fn(DateTime<Utc>) -> String) -> Vec<PeriodRollup> {
+    let mut buckets: BTreeMap<String, PeriodRollup> = BTreeMap::new();
+
+    for activity in activities {
+        let Some(date) = parsed_date(activity) else {
+            continue;
+        };
+        let period = period_key(date);
+        let entry = buckets.entry(period.clone()).or_insert_with(|| PeriodRollup {
+            period,
+            count: 0,
+            distance: 0.0,
+            moving_time: 0,
+            total_elevation_gain: 0.0,
+        });
+        entry.count += 1;
+        entry.distance += activity.distance;
+        entry.moving_time += activity.moving_time as i64;
+        entry.total_elevation_gain += activity.total_elevation_gain;
+    }
+
+    buckets.into_values().collect()
+}
+
+fn trailing_averages(activities: &[Activity]) -> TrailingAverages {
+    let cutoff = Utc::now() - Duration::weeks(TRAILING_WEEKS);
+
+    let recent: Vec<&Activity> = activities
+        .iter()
+        .filter(|a| parsed_date(a).map(|d| d >= cutoff).unwrap_or(false))
+        .collect();
+
+    if recent.is_empty() {
+        return TrailingAverages {
+            weeks: TRAILING_WEEKS,
+            average_distance: 0.0,
+            average_pace_secs_per_km: None,
+        };
+    }
+
+    let total_distance: f64 = recent.iter().map(|a| a.distance).sum();
+    let total_moving_time: i64 = recent.iter().map(|a| a.moving_time as i64).sum();
+    let average_distance = total_distance / recent.len() as f64;
+    let average_pace_secs_per_km = (total_distance > 0.0)
+        .then(|| total_moving_time as f64 / (total_distance / 1000.0));
+
+    TrailingAverages {
+        weeks: TRAILING_WEEKS,
+        average_distance,
+        average_pace_secs_per_km,
+    }
+}
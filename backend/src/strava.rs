@@ -0,0 +1,268 @@
+//! Strava API access: OAuth token exchange/refresh, fetching activities,
+//! and managing the application's push subscription.
+
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::error::{Error, StravaApiError};
+use crate::models::Activity;
+
+const OAUTH_TOKEN_URL: &str = "https://www.strava.com/oauth/token";
+const API_BASE_URL: &str = "https://www.strava.com/api/v3";
+const SUBSCRIPTIONS_URL: &str = "https://www.strava.com/api/v3/push_subscriptions";
+
+/// Parses a Strava API response, turning a non-2xx status into a
+/// `StravaApiError` carrying whatever `{"errors": [...]}` body it sent.
+async fn parse_response<T: DeserializeOwned>(response: reqwest::Response) -> Result<T, Error> {
+    if response.status().is_success() {
+        Ok(response.json().await?)
+    } else {
+        Err(StravaApiError::from_response(response).await.into())
+    }
+}
+
+/// A Strava OAuth token for a single athlete, ready to use as a bearer
+/// token against the Strava API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Token {
+    pub access_token: String,
+    pub refresh_token: String,
+    #[serde(with = "expires_at_epoch")]
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Raw shape of Strava's OAuth token response, before we normalize
+/// `expires_at` into a `DateTime<Utc>`.
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: String,
+    expires_at: i64,
+}
+
+impl From<TokenResponse> for Token {
+    fn from(resp: TokenResponse) -> Self {
+        Token {
+            access_token: resp.access_token,
+            refresh_token: resp.refresh_token,
+            expires_at: DateTime::from_timestamp(resp.expires_at, 0).unwrap_or_else(Utc::now),
+        }
+    }
+}
+
+/// (De)serializes `DateTime<Utc>` as the epoch-seconds integer Strava uses
+/// for `expires_at`, rather than chrono's default RFC 3339 string.
+mod expires_at_epoch {
+    use chrono::{DateTime, Utc};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(date: &DateTime<Utc>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_i64(date.timestamp())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let secs = i64::deserialize(deserializer)?;
+        Ok(DateTime::from_timestamp(secs, 0).unwrap_or_else(Utc::now))
+    }
+}
+
+impl Token {
+    /// True once `expires_at` has passed. Strava access tokens are valid
+    /// for six hours, so callers should check this before every API call
+    /// rather than relying on a cached "is valid" flag.
+    pub fn is_expired(&self) -> bool {
+        Utc::now() >= self.expires_at
+    }
+
+    /// Refreshes the access token in place if it has expired.
+    pub async fn refresh_if_expired(
+        &mut self,
+        client_id: &str,
+        client_secret: &str,
+    ) -> Result<(), Error> {
+        if !self.is_expired() {
+            return Ok(());
+        }
+
+        let client = reqwest::Client::new();
+        let response = client
+            .post(OAUTH_TOKEN_URL)
+            .form(&[
+                ("client_id", client_id),
+                ("client_secret", client_secret),
+                ("grant_type", "refresh_token"),
+                ("refresh_token", &self.refresh_token),
+            ])
+            .send()
+            .await?;
+        let resp: TokenResponse = parse_response(response).await?;
+
+        *self = resp.into();
+        Ok(())
+    }
+}
+
+/// Strava includes a summary of the authorizing athlete only on the
+/// initial authorization-code exchange, not on a refresh-token grant.
+#[derive(Debug, Deserialize)]
+struct ExchangeTokenResponse {
+    access_token: String,
+    refresh_token: String,
+    expires_at: i64,
+    athlete: AthleteSummary,
+}
+
+#[derive(Debug, Deserialize)]
+struct AthleteSummary {
+    id: i64,
+}
+
+impl From<ExchangeTokenResponse> for Token {
+    fn from(resp: ExchangeTokenResponse) -> Self {
+        Token {
+            access_token: resp.access_token,
+            refresh_token: resp.refresh_token,
+            expires_at: DateTime::from_timestamp(resp.expires_at, 0).unwrap_or_else(Utc::now),
+        }
+    }
+}
+
+/// Exchanges a freshly-granted OAuth `code` (from the Strava authorization
+/// redirect) for an initial access/refresh token pair, returning the id of
+/// the athlete who authorized it alongside the token.
+pub async fn exchange_token(
+    client_id: &str,
+    client_secret: &str,
+    code: &str,
+) -> Result<(i64, Token), Error> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(OAUTH_TOKEN_URL)
+        .form(&[
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+            ("code", code),
+            ("grant_type", "authorization_code"),
+        ])
+        .send()
+        .await?;
+    let resp: ExchangeTokenResponse = parse_response(response).await?;
+    let owner_id = resp.athlete.id;
+
+    Ok((owner_id, resp.into()))
+}
+
+/// Thin wrapper around the subset of the Strava v3 API this crate calls.
+pub struct StravaApi {
+    client: reqwest::Client,
+}
+
+impl Default for StravaApi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StravaApi {
+    pub fn new() -> Self {
+        StravaApi {
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Fetches a single activity by id, as the athlete identified by
+    /// `access_token`.
+    pub async fn get_activity(&self, activity_id: i64, access_token: &str) -> Result<Activity, Error> {
+        let response = self
+            .client
+            .get(format!("{}/activities/{}", API_BASE_URL, activity_id))
+            .bearer_auth(access_token)
+            .send()
+            .await?;
+
+        parse_response(response).await
+    }
+}
+
+/// A registered Strava push subscription for this application's webhook.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Subscription {
+    pub id: i64,
+    pub callback_url: String,
+}
+
+/// Strava's response to creating a push subscription is just the new
+/// subscription's id - unlike the GET endpoint, it doesn't echo back the
+/// `callback_url`.
+#[derive(Debug, Deserialize)]
+struct NewSubscription {
+    id: i64,
+}
+
+/// Registers this application's webhook as a Strava push subscription.
+/// Strava immediately issues the same `hub.*` verification GET that
+/// `webhook::verify_challenge` answers, using `STRAVA_VERIFY_TOKEN`. Returns
+/// the new subscription's id.
+pub async fn create_subscription(
+    client_id: &str,
+    client_secret: &str,
+    callback_url: &str,
+    verify_token: &str,
+) -> Result<i64, Error> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(SUBSCRIPTIONS_URL)
+        .form(&[
+            ("client_id", client_id),
+            ("client_secret", client_secret),
+            ("callback_url", callback_url),
+            ("verify_token", verify_token),
+        ])
+        .send()
+        .await?;
+
+    let created: NewSubscription = parse_response(response).await?;
+    Ok(created.id)
+}
+
+/// Returns this application's current push subscription, if one exists.
+pub async fn view_subscription(
+    client_id: &str,
+    client_secret: &str,
+) -> Result<Option<Subscription>, Error> {
+    let client = reqwest::Client::new();
+    let response = client
+        .get(SUBSCRIPTIONS_URL)
+        .query(&[("client_id", client_id), ("client_secret", client_secret)])
+        .send()
+        .await?;
+    let subscriptions: Vec<Subscription> = parse_response(response).await?;
+
+    Ok(subscriptions.into_iter().next())
+}
+
+/// Tears down the push subscription with the given id.
+pub async fn delete_subscription(
+    client_id: &str,
+    client_secret: &str,
+    subscription_id: i64,
+) -> Result<(), Error> {
+    let client = reqwest::Client::new();
+    let response = client
+        .delete(format!("{}/{}", SUBSCRIPTIONS_URL, subscription_id))
+        .query(&[("client_id", client_id), ("client_secret", client_secret)])
+        .send()
+        .await?;
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(StravaApiError::from_response(response).await.into())
+    }
+}
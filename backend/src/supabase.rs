@@ -0,0 +1,279 @@
+//! Thin REST client helpers for the Supabase tables this crate reads from
+//! and writes to: `strava_tokens`, `activities`, and `tasks`.
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::models::Activity;
+use crate::stats::StatsQuery;
+use crate::strava::Token;
+use crate::tasks::{Command, Task, TaskStatus};
+
+fn base_url() -> String {
+    std::env::var("SUPABASE_URL").unwrap_or_default()
+}
+
+fn service_key() -> String {
+    std::env::var("SUPABASE_SERVICE_KEY").unwrap_or_default()
+}
+
+fn client() -> reqwest::Client {
+    reqwest::Client::new()
+}
+
+fn authed(builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    let key = service_key();
+    builder
+        .header("apikey", &key)
+        .header("Authorization", format!("Bearer {}", key))
+}
+
+/// Turns a non-2xx Supabase response into an `Error::Supabase` carrying
+/// the status and response body, so callers see what PostgREST rejected.
+async fn check(response: reqwest::Response) -> Result<reqwest::Response, Error> {
+    if response.status().is_success() {
+        Ok(response)
+    } else {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        Err(Error::Supabase(format!("{}: {}", status, body)))
+    }
+}
+
+async fn parse_response<T: DeserializeOwned>(response: reqwest::Response) -> Result<T, Error> {
+    Ok(check(response).await?.json().await?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredToken {
+    owner_id: i64,
+    access_token: String,
+    refresh_token: String,
+    expires_at: i64,
+}
+
+/// Looks up the persisted Strava token for `owner_id`, if the athlete has
+/// completed the OAuth flow yet.
+pub async fn load_token(owner_id: i64) -> Result<Option<Token>, Error> {
+    let response = authed(client().get(format!(
+        "{}/rest/v1/strava_tokens?owner_id=eq.{}",
+        base_url(),
+        owner_id
+    )))
+    .send()
+    .await?;
+    let rows: Vec<StoredToken> = parse_response(response).await?;
+
+    Ok(rows.into_iter().next().map(|row| Token {
+        access_token: row.access_token,
+        refresh_token: row.refresh_token,
+        expires_at: chrono::DateTime::from_timestamp(row.expires_at, 0)
+            .unwrap_or_else(chrono::Utc::now),
+    }))
+}
+
+/// Upserts the token for `owner_id`, overwriting any previously stored
+/// token for that athlete.
+pub async fn save_token(owner_id: i64, token: &Token) -> Result<(), Error> {
+    let row = StoredToken {
+        owner_id,
+        access_token: token.access_token.clone(),
+        refresh_token: token.refresh_token.clone(),
+        expires_at: token.expires_at.timestamp(),
+    };
+
+    let response = authed(client().post(format!("{}/rest/v1/strava_tokens", base_url())))
+        .header("Prefer", "resolution=merge-duplicates")
+        .json(&row)
+        .send()
+        .await?;
+    check(response).await?;
+
+    Ok(())
+}
+
+/// Upserts an activity fetched from Strava into the `activities` table,
+/// merging on conflict so webhook "update" events overwrite the existing
+/// row instead of erroring.
+pub async fn upsert_activity(activity: &Activity) -> Result<(), Error> {
+    let response = authed(client().post(format!("{}/rest/v1/activities", base_url())))
+        .header("Prefer", "resolution=merge-duplicates")
+        .json(activity)
+        .send()
+        .await?;
+    check(response).await?;
+
+    Ok(())
+}
+
+/// Deletes an activity row by its Strava id.
+pub async fn delete_activity(activity_id: i64) -> Result<(), Error> {
+    let response = authed(client().delete(format!(
+        "{}/rest/v1/activities?id=eq.{}",
+        base_url(),
+        activity_id
+    )))
+    .send()
+    .await?;
+    check(response).await?;
+
+    Ok(())
+}
+
+/// Fetches the 50 most recently started activities, as raw JSON text
+/// straight from PostgREST.
+pub async fn list_recent_activities() -> Result<String, Error> {
+    let response = authed(client().get(format!(
+        "{}/rest/v1/activities?order=start_date.desc&limit=50",
+        base_url()
+    )))
+    .send()
+    .await?;
+
+    Ok(check(response).await?.text().await?)
+}
+
+/// Page size for `list_activities`' pagination loop. PostgREST caps a
+/// single response at this many rows regardless of `limit`, so fetching
+/// more than one page's worth requires looping over `offset`.
+const ACTIVITIES_PAGE_SIZE: usize = 1000;
+
+/// Fetches every activity matching a stats query's date range and
+/// activity-type filter, for server-side aggregation. Paginates through
+/// the full result set rather than capping it, since the whole point of
+/// `/api/stats` is aggregate math that has to cover every matching row.
+pub async fn list_activities(query: &StatsQuery) -> Result<Vec<Activity>, Error> {
+    let mut base_params: Vec<(&str, String)> = vec![("order", "start_date.desc".to_string())];
+
+    if let Some(from) = &query.from {
+        base_params.push(("start_date", format!("gte.{}", from)));
+    }
+    if let Some(to) = &query.to {
+        base_params.push(("start_date", format!("lte.{}", to)));
+    }
+    if let Some(activity_type) = &query.activity_type {
+        base_params.push(("type", format!("eq.{}", activity_type)));
+    }
+
+    let mut activities = Vec::new();
+    let mut offset = 0usize;
+
+    loop {
+        let mut params = base_params.clone();
+        params.push(("limit", ACTIVITIES_PAGE_SIZE.to_string()));
+        params.push(("offset", offset.to_string()));
+
+        let response = authed(client().get(format!("{}/rest/v1/activities", base_url())))
+            .query(&params)
+            .send()
+            .await?;
+        let page: Vec<Activity> = parse_response(response).await?;
+        let page_len = page.len();
+        activities.extend(page);
+
+        if page_len < ACTIVITIES_PAGE_SIZE {
+            break;
+        }
+        offset += ACTIVITIES_PAGE_SIZE;
+    }
+
+    Ok(activities)
+}
+
+#[derive(Debug, Serialize)]
+struct NewTaskRow<'a> {
+    command: &'a Command,
+    status: TaskStatus,
+    attempts: i32,
+    next_attempt_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Inserts a new, immediately-due task row.
+pub async fn insert_task(command: &Command) -> Result<(), Error> {
+    let row = NewTaskRow {
+        command,
+        status: TaskStatus::Pending,
+        attempts: 0,
+        next_attempt_at: chrono::Utc::now(),
+    };
+
+    let response = authed(client().post(format!("{}/rest/v1/tasks", base_url())))
+        .json(&row)
+        .send()
+        .await?;
+    check(response).await?;
+
+    Ok(())
+}
+
+/// Claims up to `limit` pending tasks whose `next_attempt_at` has passed,
+/// marking them in-flight so a concurrent poller doesn't pick them up too.
+pub async fn claim_due_tasks(limit: usize) -> Result<Vec<Task>, Error> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let response = authed(client().get(format!(
+        "{}/rest/v1/tasks?status=eq.pending&next_attempt_at=lte.{}&order=next_attempt_at.asc&limit={}",
+        base_url(),
+        now,
+        limit
+    )))
+    .send()
+    .await?;
+    let tasks: Vec<Task> = parse_response(response).await?;
+
+    for task in &tasks {
+        patch_task(task.id, &serde_json::json!({ "status": TaskStatus::InFlight })).await?;
+    }
+
+    Ok(tasks)
+}
+
+/// Marks a task as successfully completed.
+pub async fn mark_task_done(id: i64) -> Result<(), Error> {
+    patch_task(id, &serde_json::json!({ "status": TaskStatus::Done })).await
+}
+
+/// Marks a task as permanently failed after exhausting its retries.
+pub async fn mark_task_failed(id: i64) -> Result<(), Error> {
+    patch_task(id, &serde_json::json!({ "status": TaskStatus::Failed })).await
+}
+
+/// Schedules a retry after `backoff`, recording the new attempt count.
+pub async fn reschedule_task(id: i64, attempts: i32, backoff: std::time::Duration) -> Result<(), Error> {
+    let next_attempt_at =
+        chrono::Utc::now() + chrono::Duration::from_std(backoff).unwrap_or_default();
+    patch_task(
+        id,
+        &serde_json::json!({
+            "status": TaskStatus::Pending,
+            "attempts": attempts,
+            "next_attempt_at": next_attempt_at,
+        }),
+    )
+    .await
+}
+
+/// Puts every permanently-failed task back on the queue, attempts reset to
+/// zero, so a `ReprocessAll` command can recover from a stuck backlog.
+pub async fn requeue_failed_tasks() -> Result<(), Error> {
+    let response = authed(client().patch(format!("{}/rest/v1/tasks?status=eq.failed", base_url())))
+        .json(&serde_json::json!({
+            "status": TaskStatus::Pending,
+            "attempts": 0,
+            "next_attempt_at": chrono::Utc::now(),
+        }))
+        .send()
+        .await?;
+    check(response).await?;
+
+    Ok(())
+}
+
+async fn patch_task(id: i64, body: &serde_json::Value) -> Result<(), Error> {
+    let response = authed(client().patch(format!("{}/rest/v1/tasks?id=eq.{}", base_url(), id)))
+        .json(body)
+        .send()
+        .await?;
+    check(response).await?;
+
+    Ok(())
+}
@@ -0,0 +1,150 @@
+//! An async task queue for webhook-triggered work. Webhook handlers enqueue
+//! a `Command` and return immediately; `run_worker` polls the `tasks` table
+//! separately and executes commands with retries and backoff, so a slow or
+//! failing Strava call never blocks the webhook acknowledgement.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Semaphore;
+
+use crate::error::Error;
+use crate::strava::StravaApi;
+use crate::supabase;
+
+const MAX_ATTEMPTS: i32 = 5;
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+const MAX_CONCURRENT_TASKS: usize = 4;
+/// How often `run_worker` enqueues a `Command::ReprocessAll` of its own
+/// accord, so activities that exhausted their retries still get another
+/// pass without anyone having to trigger it by hand.
+const REPROCESS_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Work enqueued from a webhook event, or by `run_worker`'s periodic
+/// reprocessing pass, persisted in the `tasks` table until it runs to completion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum Command {
+    ImportActivity { owner_id: i64, object_id: i64 },
+    DeleteActivity { object_id: i64 },
+    ReprocessAll,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatus {
+    Pending,
+    InFlight,
+    Done,
+    Failed,
+}
+
+/// A row in the `tasks` table: a command plus its retry bookkeeping.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Task {
+    pub id: i64,
+    pub command: Command,
+    pub attempts: i32,
+}
+
+/// Enqueues a command for the worker loop to pick up, returning
+/// immediately so webhook handlers can acknowledge Strava without waiting
+/// on the Strava API.
+pub async fn enqueue(command: Command) -> Result<(), Error> {
+    supabase::insert_task(&command).await
+}
+
+/// Runs forever, polling the `tasks` table every `POLL_INTERVAL` and
+/// driving up to `MAX_CONCURRENT_TASKS` commands at a time.
+pub async fn run_worker() {
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_TASKS));
+    let mut last_reprocess = Instant::now();
+
+    loop {
+        match supabase::claim_due_tasks(MAX_CONCURRENT_TASKS).await {
+            Ok(tasks) => {
+                for task in tasks {
+                    let permit = semaphore.clone().acquire_owned().await.unwrap();
+                    tokio::spawn(async move {
+                        run_task(task).await;
+                        drop(permit);
+                    });
+                }
+            }
+            Err(e) => tracing::error!("failed to poll tasks: {}", e),
+        }
+
+        if last_reprocess.elapsed() >= REPROCESS_INTERVAL {
+            if let Err(e) = enqueue(Command::ReprocessAll).await {
+                tracing::error!("failed to enqueue periodic reprocess: {}", e);
+            }
+            last_reprocess = Instant::now();
+        }
+
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+async fn run_task(task: Task) {
+    match execute(&task.command).await {
+        Ok(()) => {
+            if let Err(e) = supabase::mark_task_done(task.id).await {
+                tracing::error!("failed to mark task {} done: {}", task.id, e);
+            }
+        }
+        Err(e) if task.attempts + 1 >= MAX_ATTEMPTS => {
+            tracing::error!(
+                "task {} failed permanently after {} attempts: {}",
+                task.id,
+                task.attempts + 1,
+                e
+            );
+            if let Err(e) = supabase::mark_task_failed(task.id).await {
+                tracing::error!("failed to mark task {} failed: {}", task.id, e);
+            }
+        }
+        Err(e) => {
+            let backoff = Duration::from_secs(2u64.saturating_pow((task.attempts + 1) as u32));
+            tracing::warn!(
+                "task {} failed (attempt {}): {}, retrying in {:?}",
+                task.id,
+                task.attempts + 1,
+                e,
+                backoff
+            );
+            if let Err(e) = supabase::reschedule_task(task.id, task.attempts + 1, backoff).await {
+                tracing::error!("failed to reschedule task {}: {}", task.id, e);
+            }
+        }
+    }
+}
+
+async fn execute(command: &Command) -> Result<(), Error> {
+    match command {
+        Command::ImportActivity {
+            owner_id,
+            object_id,
+        } => {
+            let Some(mut token) = supabase::load_token(*owner_id).await? else {
+                tracing::warn!("no Strava token stored for athlete {}", owner_id);
+                return Ok(());
+            };
+
+            let was_expired = token.is_expired();
+            let client_id = std::env::var("STRAVA_CLIENT_ID").unwrap_or_default();
+            let client_secret = std::env::var("STRAVA_CLIENT_SECRET").unwrap_or_default();
+            token.refresh_if_expired(&client_id, &client_secret).await?;
+            if was_expired {
+                supabase::save_token(*owner_id, &token).await?;
+            }
+
+            let activity = StravaApi::new()
+                .get_activity(*object_id, &token.access_token)
+                .await?;
+            supabase::upsert_activity(&activity).await
+        }
+        Command::DeleteActivity { object_id } => supabase::delete_activity(*object_id).await,
+        Command::ReprocessAll => supabase::requeue_failed_tasks().await,
+    }
+}
@@ -1,18 +1,20 @@
-use axum::{
-    extract::Query,
-    http::StatusCode,
-    Json,
-};
+//! Shared Strava webhook logic: event and verification types, plus the
+//! create/update/delete dispatch that both the Axum dev server and the
+//! Vercel functions drive.
+
 use serde::{Deserialize, Serialize};
 
+use crate::error::Error;
+use crate::tasks::{self, Command};
+
 #[derive(Debug, Deserialize)]
 pub struct WebhookVerification {
     #[serde(rename = "hub.mode")]
-    pub mode: String,
+    pub mode: Option<String>,
     #[serde(rename = "hub.challenge")]
-    pub challenge: String,
+    pub challenge: Option<String>,
     #[serde(rename = "hub.verify_token")]
-    pub verify_token: String,
+    pub verify_token: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -32,50 +34,40 @@ pub struct WebhookEvent {
     pub updates: Option<serde_json::Value>,
 }
 
-/// GET /api/webhook - Strava webhook verification
-pub async fn verify(
-    Query(params): Query<WebhookVerification>,
-) -> Result<Json<VerificationResponse>, StatusCode> {
-    let verify_token = std::env::var("STRAVA_VERIFY_TOKEN").unwrap_or_default();
-
-    if params.mode == "subscribe" && params.verify_token == verify_token {
-        tracing::info!("Webhook verified successfully");
-        Ok(Json(VerificationResponse {
-            challenge: params.challenge,
-        }))
-    } else {
-        tracing::warn!("Webhook verification failed");
-        Err(StatusCode::FORBIDDEN)
+/// Checks a `hub.*` verification request against the configured verify
+/// token, returning the challenge to echo back on success.
+pub fn verify_challenge(params: &WebhookVerification, expected_token: &str) -> Option<String> {
+    match (
+        params.mode.as_deref(),
+        params.verify_token.as_deref(),
+        &params.challenge,
+    ) {
+        (Some("subscribe"), Some(token), Some(challenge)) if token == expected_token => {
+            Some(challenge.clone())
+        }
+        _ => None,
     }
 }
 
-/// POST /api/webhook - Receive Strava webhook events
-pub async fn handle(
-    Json(event): Json<WebhookEvent>,
-) -> StatusCode {
-    tracing::info!("Received webhook event: {:?}", event);
-
-    // Only process activity events
+/// Translates a webhook event into a `Command` and enqueues it for the
+/// worker loop, so the handler can acknowledge Strava immediately instead
+/// of waiting on the Strava API call itself. No-op for anything but
+/// activity events.
+pub async fn dispatch(event: &WebhookEvent) -> Result<(), Error> {
     if event.object_type != "activity" {
-        return StatusCode::OK;
+        return Ok(());
     }
 
-    match event.aspect_type.as_str() {
-        "create" | "update" => {
-            // TODO: Fetch full activity from Strava API
-            // TODO: Store activity in Supabase
-            tracing::info!(
-                "Processing activity {} for athlete {}",
-                event.object_id,
-                event.owner_id
-            );
-        }
-        "delete" => {
-            // TODO: Handle activity deletion
-            tracing::info!("Activity {} deleted", event.object_id);
-        }
-        _ => {}
-    }
+    let command = match event.aspect_type.as_str() {
+        "create" | "update" => Command::ImportActivity {
+            owner_id: event.owner_id,
+            object_id: event.object_id,
+        },
+        "delete" => Command::DeleteActivity {
+            object_id: event.object_id,
+        },
+        _ => return Ok(()),
+    };
 
-    StatusCode::OK
+    tasks::enqueue(command).await
 }